@@ -2,6 +2,7 @@
 mod solver;
 
 use solver::{Solver, SolverInput, SolutionStep};
+use tauri::{AppHandle, Emitter};
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -10,10 +11,15 @@ fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-async fn solve_puzzle(input: SolverInput) -> Option<Vec<SolutionStep>> {
+async fn solve_puzzle(app: AppHandle, input: SolverInput) -> Option<Vec<SolutionStep>> {
     tauri::async_runtime::spawn_blocking(move || {
         match Solver::new(input) {
-            Ok(mut s) => s.solve(),
+            Ok(mut s) => {
+                let mut on_progress = |event: solver::ProgressEvent| {
+                    let _ = app.emit("solve-progress", event);
+                };
+                s.solve(&mut on_progress)
+            }
             Err(e) => {
                 println!("Solver error: {}", e);
                 None
@@ -22,6 +28,19 @@ async fn solve_puzzle(input: SolverInput) -> Option<Vec<SolutionStep>> {
     }).await.unwrap_or(None)
 }
 
+#[tauri::command]
+async fn solve_puzzle_all(input: SolverInput, limit: usize) -> Vec<Vec<SolutionStep>> {
+    tauri::async_runtime::spawn_blocking(move || {
+        match Solver::new(input) {
+            Ok(mut s) => s.solve_all(limit),
+            Err(e) => {
+                println!("Solver error: {}", e);
+                Vec::new()
+            }
+        }
+    }).await.unwrap_or_default()
+}
+
 #[tauri::command]
 fn cancel_solve() {
     solver::CANCEL_FLAG.store(true, std::sync::atomic::Ordering::SeqCst);
@@ -31,7 +50,7 @@ fn cancel_solve() {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet, solve_puzzle, cancel_solve])
+        .invoke_handler(tauri::generate_handler![greet, solve_puzzle, solve_puzzle_all, cancel_solve])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BinaryHeap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicBool, Ordering};
 
 pub static CANCEL_FLAG: AtomicBool = AtomicBool::new(false);
@@ -11,6 +14,34 @@ pub struct SolverInput {
     pub grid: Vec<u8>,
     pub goal: u8,
     pub shapes: Vec<ShapeData>,
+    #[serde(default)]
+    pub mode: SolverMode,
+    #[serde(default)]
+    pub deadline_millis: Option<u64>,
+    /// Per-cell target, one entry per `grid` cell. Falls back to the scalar
+    /// `goal` for every cell when absent.
+    #[serde(default)]
+    pub goals: Option<Vec<u8>>,
+}
+
+/// Search strategy used by `Solver::solve`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum SolverMode {
+    /// Exhaustive DFS backtracking over the shape order the constructor produces.
+    #[default]
+    Dfs,
+    /// Layered beam search that keeps only the `width` most promising partial
+    /// assignments at each layer; fast but not guaranteed to find a solution
+    /// even when one exists.
+    Beam { width: usize },
+    /// Simulated annealing over a full one-placement-per-shape assignment;
+    /// for boards where backtracking DFS never returns.
+    Anneal { millis: u64 },
+    /// Exact search like `Dfs`, but expansion order is guided by a
+    /// best-first heuristic instead of fixed shape order, so branches that
+    /// strand an unreachable cell get pruned long before the leaves.
+    BestFirst,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +49,14 @@ pub struct SolverInput {
 pub struct ShapeData {
     pub id: usize,
     pub points: Vec<usize>,
+    /// `-1` to decrement covered cells on stamp (the original behavior),
+    /// `1` to increment them instead.
+    #[serde(default = "default_direction")]
+    pub direction: i8,
+}
+
+fn default_direction() -> i8 {
+    -1
 }
 
 #[derive(Debug, Serialize, Clone, Copy)]
@@ -29,6 +68,18 @@ pub struct SolutionStep {
     pub placement_seq: usize,
 }
 
+/// Live status of an in-progress `Dfs`, `Beam`, or `BestFirst` run, emitted
+/// every 1024 iterations so the frontend can render a progress bar.
+/// `Anneal` has its own millis-bounded budget and a cost curve that doesn't
+/// map onto `depth_reached`/`best_cells_remaining`, so it does not emit this.
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressEvent {
+    pub iterations: u64,
+    pub depth_reached: usize,
+    pub best_cells_remaining: usize,
+}
+
 #[derive(Clone)]
 struct Shape {
     original_id: usize,
@@ -39,6 +90,11 @@ struct Shape {
     seq: usize,
     incs: i32,
     equivalent_to: Option<usize>,
+    /// Cells this shape can cover under *some* placement, used to cheaply
+    /// prove a residual cell unreachable by the remaining shapes.
+    reach: Vec<bool>,
+    /// `-1` decrements covered cells on stamp, `1` increments them.
+    direction: i8,
 }
 
 pub struct Solver {
@@ -46,6 +102,113 @@ pub struct Solver {
     mat: Vec<i8>,
     shapes: Vec<Shape>,
     ns: usize,
+    mode: SolverMode,
+    deadline_millis: Option<u64>,
+    /// Whether the shared wrap-budget check in `can_place` is safe to prune
+    /// on: sound only when every shape wraps the same way, since a mixed
+    /// set's required decrement/increment wrap totals aren't individually
+    /// bounded by `incs` (only their difference is), so pruning on either
+    /// one alone can discard a placement a full, mixed-direction solve
+    /// still needs.
+    budget_prunable: bool,
+}
+
+/// A partial assignment explored by `Solver::solve_beam`.
+#[derive(Clone)]
+struct BeamNode {
+    seq: Vec<usize>,
+    mat: Vec<i8>,
+    budget: i32,
+    score: i64,
+}
+
+/// A partial assignment explored by `Solver::solve_best_first`, ordered by
+/// `score` so `BinaryHeap` pops the most promising node first.
+struct BestFirstNode {
+    i: usize,
+    seq: Vec<usize>,
+    mat: Vec<i8>,
+    budget: i32,
+    score: i64,
+}
+
+impl PartialEq for BestFirstNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+
+impl Eq for BestFirstNode {}
+
+impl PartialOrd for BestFirstNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BestFirstNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score.cmp(&other.score)
+    }
+}
+
+/// Minimal xorshift64 RNG, good enough for annealing proposals without
+/// pulling in a dependency.
+struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        XorShift { state: seed | 1 }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    fn next_range(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+/// Applies one shape's stamp to a residual value: decrement-with-wrap for a
+/// `direction < 0` shape (the original behavior), increment-with-wrap for a
+/// `direction > 0` one.
+#[inline]
+fn stamp_value(v: i8, mt: i8, direction: i8) -> i8 {
+    if direction < 0 {
+        if v == 0 { mt - 1 } else { v - 1 }
+    } else {
+        if v + 1 == mt { 0 } else { v + 1 }
+    }
+}
+
+/// Reverses `stamp_value` for the same shape.
+#[inline]
+fn unstamp_value(v: i8, mt: i8, direction: i8) -> i8 {
+    stamp_value(v, mt, -direction)
+}
+
+/// Pointer-based `stamp_value`, for the raw-pointer backtracking loops.
+#[inline]
+unsafe fn stamp_cell(val_ptr: *mut i8, mt: i8, direction: i8) {
+    *val_ptr = stamp_value(*val_ptr, mt, direction);
+}
+
+/// Pointer-based `unstamp_value`.
+#[inline]
+unsafe fn unstamp_cell(val_ptr: *mut i8, mt: i8, direction: i8) {
+    *val_ptr = unstamp_value(*val_ptr, mt, direction);
 }
 
 impl Solver {
@@ -55,15 +218,30 @@ impl Solver {
         let lt = input.grid.iter().copied().max().unwrap_or(0) as i32;
         let mt = lt + 1;
         let goal = input.goal as i32;
-        let mut new_order = vec![0i32; mt as usize];
-        for i in 0..mt {
-            new_order[i as usize] = if i <= goal { goal - i } else { goal + mt - i };
-        }
-        let mat: Vec<i8> = input.grid.iter().map(|&v| new_order[v as usize] as i8).collect();
+        let goals: Vec<i32> = match &input.goals {
+            Some(g) if g.len() != input.grid.len() => {
+                return Err(format!("goals has {} entries, expected {}", g.len(), input.grid.len()));
+            }
+            Some(g) if g.iter().any(|&v| (v as i32) >= mt) => {
+                return Err(format!("goals must be < {} (the grid's max value + 1)", mt));
+            }
+            Some(g) => g.iter().map(|&v| v as i32).collect(),
+            None => vec![goal; input.grid.len()],
+        };
+        let mat: Vec<i8> = input
+            .grid
+            .iter()
+            .zip(goals.iter())
+            .map(|(&v, &g)| {
+                let v = v as i32;
+                (if v <= g { g - v } else { g + mt - v }) as i8
+            })
+            .collect();
         let ns = input.shapes.len();
         if ns == 0 { return Err("No shapes".to_string()); }
 
-        let mut work: Vec<(usize, Vec<usize>)> = input.shapes.iter().map(|s| (s.id, s.points.clone())).collect();
+        let mut work: Vec<(usize, Vec<usize>, i8)> =
+            input.shapes.iter().map(|s| (s.id, s.points.clone(), s.direction)).collect();
         let mut shapes: Vec<Shape> = Vec::with_capacity(ns);
         let mut togs = 0i32;
 
@@ -72,9 +250,11 @@ impl Solver {
             for j in 0..=i {
                 if work[j].1.len() > work[best].1.len() { best = j; }
             }
-            let (orig_id, mut pts) = work.swap_remove(best);
+            let (orig_id, mut pts, raw_direction) = work.swap_remove(best);
+            // Normalize to exactly -1/1 so `stamp_value`/`unstamp_value` stay exact inverses.
+            let direction: i8 = if raw_direction < 0 { -1 } else { 1 };
             pts.sort_unstable();
-            togs += pts.len() as i32;
+            togs += pts.len() as i32 * -(direction as i32);
 
             let mut max_x = 0;
             let mut max_y = 0;
@@ -96,50 +276,105 @@ impl Solver {
 
             let mut eq_to = None;
             for (idx, prev) in shapes.iter().enumerate() {
-                if prev.npts == pts.len() && prev.tot == tot && prev.cache[..prev.npts] == cache[..prev.npts] {
+                if prev.npts == pts.len() && prev.tot == tot && prev.direction == direction
+                    && prev.cache[..prev.npts] == cache[..prev.npts]
+                {
                     eq_to = Some(idx);
                     break;
                 }
             }
 
+            let mut reach = vec![false; x * y];
+            for &m in &cache {
+                reach[m] = true;
+            }
+
             shapes.push(Shape {
                 original_id: orig_id,
                 npts: pts.len(),
                 ax, tot, cache,
                 seq: 0, incs: 0,
                 equivalent_to: eq_to,
+                reach,
+                direction,
             });
         }
 
-        let grid_sum: i32 = mat.iter().map(|&v| v as i32).sum();
-        shapes[0].incs = (togs - grid_sum) / mt;
+        // Per-cell residual sum, generalized from the old single grid_sum/goal
+        // relationship: each cell's entry in `mat` already reflects its own
+        // target, so summing `mat` directly gives the per-cell residual total.
+        let residual_sum: i32 = mat.iter().map(|&v| v as i32).sum();
+        let incs_raw = (togs - residual_sum) / mt;
+        let budget_prunable = shapes.iter().all(|s| s.direction == shapes[0].direction);
+        // `incs_raw` is the total decrement-wrap count a fully decrement
+        // solve needs; a fully increment solve needs the same count but of
+        // increment-wraps, so the seed flips sign to match.
+        shapes[0].incs = if !budget_prunable {
+            0
+        } else if shapes[0].direction < 0 {
+            incs_raw
+        } else {
+            -incs_raw
+        };
 
-        Ok(Solver { max_token: mt, mat, shapes, ns })
+        Ok(Solver { max_token: mt, mat, shapes, ns, mode: input.mode, deadline_millis: input.deadline_millis, budget_prunable })
     }
 
-    pub fn solve(&mut self) -> Option<Vec<SolutionStep>> {
+    pub fn solve(&mut self, on_progress: &mut dyn FnMut(ProgressEvent)) -> Option<Vec<SolutionStep>> {
+        match self.mode {
+            SolverMode::Dfs => self.solve_dfs(on_progress),
+            SolverMode::Beam { width } => self.solve_beam(width, on_progress),
+            SolverMode::Anneal { millis } => self.solve_anneal(millis),
+            SolverMode::BestFirst => self.solve_best_first(on_progress),
+        }
+    }
+
+    fn solve_dfs(&mut self, on_progress: &mut dyn FnMut(ProgressEvent)) -> Option<Vec<SolutionStep>> {
         CANCEL_FLAG.store(false, Ordering::SeqCst);
         let ns = self.ns;
         let mt = self.max_token as i8;
-        let mut i = 0; 
-        
+        let budget_prunable = self.budget_prunable;
+        let mut i = 0;
+
         let mat_ptr = self.mat.as_mut_ptr();
         let shapes_ptr = self.shapes.as_mut_ptr();
 
         let mut budget_stack = [0i32; 256];
         unsafe { budget_stack[0] = (*shapes_ptr).incs; }
 
-        let mut iter_count = 0;
+        let mut iter_count: u64 = 0;
+        let start = std::time::Instant::now();
+        let mut deepest_i = 0usize;
+        let mut best_cells_remaining = self.mat.iter().filter(|&&v| v != 0).count();
 
         loop {
             iter_count += 1;
-            if iter_count % 1024 == 0 && CANCEL_FLAG.load(Ordering::Relaxed) {
-                return None;
+            if iter_count % 1024 == 0 {
+                if CANCEL_FLAG.load(Ordering::Relaxed) {
+                    return None;
+                }
+                if let Some(deadline) = self.deadline_millis {
+                    if start.elapsed().as_millis() as u64 > deadline {
+                        return None;
+                    }
+                }
+                let cells_remaining = self.mat.iter().filter(|&&v| v != 0).count();
+                if i > deepest_i {
+                    deepest_i = i;
+                    best_cells_remaining = cells_remaining;
+                } else if i == deepest_i {
+                    best_cells_remaining = best_cells_remaining.min(cells_remaining);
+                }
+                on_progress(ProgressEvent {
+                    iterations: iter_count,
+                    depth_reached: deepest_i,
+                    best_cells_remaining,
+                });
             }
 
-            let (npts, tot, seq, cache_ptr, i_budget) = unsafe {
+            let (npts, tot, seq, cache_ptr, i_budget, direction) = unsafe {
                 let shape = &*shapes_ptr.add(i);
-                (shape.npts, shape.tot, shape.seq, shape.cache.as_ptr(), budget_stack[i])
+                (shape.npts, shape.tot, shape.seq, shape.cache.as_ptr(), budget_stack[i], shape.direction)
             };
 
             let mut ok = false;
@@ -147,12 +382,14 @@ impl Solver {
                 let tci = s * npts;
                 let mut ti = i_budget;
                 let mut can_place = true;
-                
+
                 unsafe {
                     let pts_start = cache_ptr.add(tci);
                     for j in 0..npts {
                         let mi = *pts_start.add(j);
-                        if *mat_ptr.add(mi) == 0 {
+                        let val = *mat_ptr.add(mi);
+                        let wraps = if direction < 0 { val == 0 } else { val == mt - 1 };
+                        if wraps && budget_prunable {
                             ti -= 1;
                             if ti < 0 {
                                 can_place = false;
@@ -169,10 +406,7 @@ impl Solver {
                         let pts_apply = cache_ptr.add(tci);
                         for j in 0..npts {
                             let mi = *pts_apply.add(j);
-                            let val_ptr = mat_ptr.add(mi);
-                            let mut v = *val_ptr - 1;
-                            if v < 0 { v = mt - 1; }
-                            *val_ptr = v;
+                            stamp_cell(mat_ptr.add(mi), mt, direction);
                         }
                         ok = true;
                     }
@@ -181,7 +415,28 @@ impl Solver {
             }
 
             if ok {
-                if i == ns - 1 { return Some(self.build_solution()); }
+                if i == ns - 1 {
+                    if self.mat.iter().all(|&v| v == 0) {
+                        return Some(self.build_solution());
+                    }
+                    // Every shape is placed but the board can still be unsolved: the wrap
+                    // budget only forces a zeroed board when every shape decrements (for
+                    // an all-increment set it is an upper bound only, and a mixed set is
+                    // not budgeted at all). Undo this placement and try the next one.
+                    unsafe {
+                        let shape_mut = &mut *shapes_ptr.add(i);
+                        let s = shape_mut.seq;
+                        let p_npts = shape_mut.npts;
+                        let p_direction = shape_mut.direction;
+                        let p_cache = shape_mut.cache.as_ptr().add(s * p_npts);
+                        for j in 0..p_npts {
+                            let mi = *p_cache.add(j);
+                            unstamp_cell(mat_ptr.add(mi), mt, p_direction);
+                        }
+                        shape_mut.seq += 1;
+                    }
+                    continue;
+                }
                 i += 1;
                 unsafe {
                     let next_shape = &mut *shapes_ptr.add(i);
@@ -191,19 +446,161 @@ impl Solver {
             } else {
                 if i == 0 { return None; }
                 i -= 1;
-                
+
+                unsafe {
+                    let prev_shape = &mut *shapes_ptr.add(i);
+                    let p_seq = prev_shape.seq;
+                    let p_npts = prev_shape.npts;
+                    let p_direction = prev_shape.direction;
+                    let p_cache = prev_shape.cache.as_ptr().add(p_seq * p_npts);
+
+                    for j in 0..p_npts {
+                        let mi = *p_cache.add(j);
+                        unstamp_cell(mat_ptr.add(mi), mt, p_direction);
+                    }
+                    prev_shape.seq += 1;
+                }
+            }
+        }
+    }
+
+    /// Exact backtracking like `solve_dfs`, but a complete board is recorded
+    /// rather than returned: the last shape's placement is then undone and
+    /// the search resumes as if it had failed, until `limit` solutions are
+    /// collected or the space is exhausted. Each shape's placement is floored
+    /// at that of the nearest earlier shape it is interchangeable with, which
+    /// keeps every group of equivalent shapes non-decreasing in placement
+    /// order, so permutations of interchangeable shapes are never enumerated
+    /// as distinct solutions.
+    pub fn solve_all(&mut self, limit: usize) -> Vec<Vec<SolutionStep>> {
+        CANCEL_FLAG.store(false, Ordering::SeqCst);
+        let mut solutions = Vec::new();
+        if limit == 0 {
+            return solutions;
+        }
+
+        let ns = self.ns;
+        let mt = self.max_token as i8;
+        let budget_prunable = self.budget_prunable;
+        let mut i = 0;
+
+        let mat_ptr = self.mat.as_mut_ptr();
+        let shapes_ptr = self.shapes.as_mut_ptr();
+
+        let mut budget_stack = [0i32; 256];
+        unsafe { budget_stack[0] = (*shapes_ptr).incs; }
+
+        let mut iter_count: u64 = 0;
+        let start = std::time::Instant::now();
+
+        loop {
+            iter_count += 1;
+            if iter_count % 1024 == 0 {
+                if CANCEL_FLAG.load(Ordering::Relaxed) {
+                    return solutions;
+                }
+                if let Some(deadline) = self.deadline_millis {
+                    if start.elapsed().as_millis() as u64 > deadline {
+                        return solutions;
+                    }
+                }
+            }
+
+            let (npts, tot, seq, cache_ptr, i_budget, direction) = unsafe {
+                let shape = &*shapes_ptr.add(i);
+                (shape.npts, shape.tot, shape.seq, shape.cache.as_ptr(), budget_stack[i], shape.direction)
+            };
+
+            let mut ok = false;
+            for s in seq..tot {
+                let tci = s * npts;
+                let mut ti = i_budget;
+                let mut can_place = true;
+
+                unsafe {
+                    let pts_start = cache_ptr.add(tci);
+                    for j in 0..npts {
+                        let mi = *pts_start.add(j);
+                        let val = *mat_ptr.add(mi);
+                        let wraps = if direction < 0 { val == 0 } else { val == mt - 1 };
+                        if wraps && budget_prunable {
+                            ti -= 1;
+                            if ti < 0 {
+                                can_place = false;
+                                break;
+                            }
+                        }
+                    }
+
+                    if can_place {
+                        let shape_mut = &mut *shapes_ptr.add(i);
+                        shape_mut.seq = s;
+                        budget_stack[i + 1] = ti;
+
+                        let pts_apply = cache_ptr.add(tci);
+                        for j in 0..npts {
+                            let mi = *pts_apply.add(j);
+                            stamp_cell(mat_ptr.add(mi), mt, direction);
+                        }
+                        ok = true;
+                    }
+                }
+                if ok { break; }
+            }
+
+            if ok && i == ns - 1 {
+                if self.mat.iter().all(|&v| v == 0) {
+                    solutions.push(self.build_solution());
+                    if solutions.len() >= limit {
+                        return solutions;
+                    }
+                }
+                unsafe {
+                    let shape_mut = &mut *shapes_ptr.add(i);
+                    let s_seq = shape_mut.seq;
+                    let s_npts = shape_mut.npts;
+                    let s_direction = shape_mut.direction;
+                    let s_cache = shape_mut.cache.as_ptr().add(s_seq * s_npts);
+                    for j in 0..s_npts {
+                        let mi = *s_cache.add(j);
+                        unstamp_cell(mat_ptr.add(mi), mt, s_direction);
+                    }
+                    shape_mut.seq += 1;
+                }
+                continue;
+            }
+
+            if ok {
+                i += 1;
+                unsafe {
+                    // `equivalent_to` names the group's first member; floor on the
+                    // nearest earlier member instead so groups of 3+ stay ordered.
+                    let floor_idx = (*shapes_ptr.add(i)).equivalent_to.map(|ei| {
+                        ((ei + 1)..i)
+                            .rev()
+                            .find(|&j| (*shapes_ptr.add(j)).equivalent_to == Some(ei))
+                            .unwrap_or(ei)
+                    });
+                    let eq_seq = floor_idx.map(|fi| (*shapes_ptr.add(fi)).seq).unwrap_or(0);
+                    let next_shape = &mut *shapes_ptr.add(i);
+                    next_shape.seq = eq_seq;
+                }
+            } else {
+                if i == 0 {
+                    return solutions;
+                }
+                i -= 1;
+
                 unsafe {
                     let prev_shape = &mut *shapes_ptr.add(i);
                     let p_seq = prev_shape.seq;
                     let p_npts = prev_shape.npts;
+                    let p_direction = prev_shape.direction;
                     let p_cache = prev_shape.cache.as_ptr().add(p_seq * p_npts);
-                    
+
                     for j in 0..p_npts {
                         let mi = *p_cache.add(j);
-                        let val_ptr = mat_ptr.add(mi);
-                        let mut v = *val_ptr + 1;
-                        if v == mt { v = 0; }
-                        *val_ptr = v;
+                        unstamp_cell(mat_ptr.add(mi), mt, p_direction);
                     }
                     prev_shape.seq += 1;
                 }
@@ -225,4 +622,379 @@ impl Solver {
         }
         steps
     }
+
+    /// Approximate solve: keeps only the `width` most promising partial
+    /// assignments at each layer instead of exploring the full tree. Returns
+    /// `None` if every candidate gets pruned before the last layer.
+    fn solve_beam(&mut self, width: usize, on_progress: &mut dyn FnMut(ProgressEvent)) -> Option<Vec<SolutionStep>> {
+        CANCEL_FLAG.store(false, Ordering::SeqCst);
+        let ns = self.ns;
+        let mt = self.max_token as i8;
+        let budget_prunable = self.budget_prunable;
+        let total_cells = self.mat.len();
+        let mut iter_count: u64 = 0;
+        let start = std::time::Instant::now();
+
+        // reachable_from[i][c] = some shape in shapes[i..ns] can cover cell c.
+        let mut reachable_from = vec![vec![false; total_cells]; ns + 1];
+        for i in (0..ns).rev() {
+            let mut r = reachable_from[i + 1].clone();
+            for (c, covers) in self.shapes[i].reach.iter().enumerate() {
+                if *covers {
+                    r[c] = true;
+                }
+            }
+            reachable_from[i] = r;
+        }
+
+        let mut nodes = vec![BeamNode {
+            seq: Vec::with_capacity(ns),
+            mat: self.mat.clone(),
+            budget: self.shapes[0].incs,
+            score: 0,
+        }];
+
+        for i in 0..ns {
+            let npts = self.shapes[i].npts;
+            let tot = self.shapes[i].tot;
+            let cache = self.shapes[i].cache.clone();
+            let eq_floor = self.shapes[i].equivalent_to;
+            let direction = self.shapes[i].direction;
+
+            let mut children: Vec<BeamNode> = Vec::new();
+            let mut seen: HashSet<u64> = HashSet::new();
+            let best_cells_remaining = nodes.iter().map(|n| n.mat.iter().filter(|&&v| v != 0).count()).min().unwrap_or(0);
+
+            for node in &nodes {
+                let min_s = eq_floor.map(|ei| node.seq[ei]).unwrap_or(0);
+                for s in min_s..tot {
+                    iter_count += 1;
+                    if iter_count % 1024 == 0 {
+                        if CANCEL_FLAG.load(Ordering::Relaxed) {
+                            return None;
+                        }
+                        if let Some(deadline) = self.deadline_millis {
+                            if start.elapsed().as_millis() as u64 > deadline {
+                                return None;
+                            }
+                        }
+                        on_progress(ProgressEvent {
+                            iterations: iter_count,
+                            depth_reached: i,
+                            best_cells_remaining,
+                        });
+                    }
+
+                    let tci = s * npts;
+                    let mut ti = node.budget;
+                    let mut can_place = true;
+                    for j in 0..npts {
+                        let val = node.mat[cache[tci + j]];
+                        let wraps = if direction < 0 { val == 0 } else { val == mt - 1 };
+                        if wraps && budget_prunable {
+                            ti -= 1;
+                            if ti < 0 {
+                                can_place = false;
+                                break;
+                            }
+                        }
+                    }
+                    if !can_place {
+                        continue;
+                    }
+
+                    let mut mat = node.mat.clone();
+                    for j in 0..npts {
+                        let mi = cache[tci + j];
+                        mat[mi] = stamp_value(mat[mi], mt, direction);
+                    }
+
+                    let mut hasher = DefaultHasher::new();
+                    mat.hash(&mut hasher);
+                    if !seen.insert(hasher.finish()) {
+                        continue;
+                    }
+
+                    let mut seq = node.seq.clone();
+                    seq.push(s);
+
+                    if i == ns - 1 && mat.iter().all(|&v| v == 0) {
+                        return Some(self.build_solution_from_seq(&seq));
+                    }
+
+                    let score = Self::infeasibility_score(&mat, &reachable_from[i + 1]);
+                    children.push(BeamNode { seq, mat, budget: ti, score });
+                }
+            }
+
+            if children.is_empty() {
+                return None;
+            }
+            children.sort_by_key(|c| c.score);
+            children.truncate(width);
+            nodes = children;
+        }
+
+        None
+    }
+
+    /// Cheap infeasibility heuristic: cells that still need covering but that
+    /// no remaining shape can ever reach cost their residual value, plus one
+    /// per such cell. Zero means "nothing provably wrong yet".
+    fn infeasibility_score(mat: &[i8], reachable: &[bool]) -> i64 {
+        let mut score = 0i64;
+        for (c, &v) in mat.iter().enumerate() {
+            if v != 0 && !reachable[c] {
+                score += v as i64 + 1;
+            }
+        }
+        score
+    }
+
+    /// Simulated annealing over one placement per shape. `coverage[c]` is the
+    /// net stamp count on cell `c`: decrementing shapes covering it minus
+    /// incrementing ones; the board is solved when
+    /// `(coverage[c] - mat[c]) % mt == 0` everywhere, which honors the same
+    /// wraparound budget the DFS path tracks explicitly. Returns the first
+    /// zero-cost assignment found, or `None` if none is found before `millis`
+    /// elapses or the solve is cancelled.
+    fn solve_anneal(&mut self, millis: u64) -> Option<Vec<SolutionStep>> {
+        CANCEL_FLAG.store(false, Ordering::SeqCst);
+        let ns = self.ns;
+        let mt = self.max_token;
+        let mut rng = XorShift::new(0x9E3779B97F4A7C15 ^ (ns as u64).wrapping_add(1));
+
+        let mut assignment: Vec<usize> = (0..ns).map(|i| rng.next_range(self.shapes[i].tot)).collect();
+        let mut coverage = vec![0i32; self.mat.len()];
+        for i in 0..ns {
+            let npts = self.shapes[i].npts;
+            let tci = assignment[i] * npts;
+            let contrib = -(self.shapes[i].direction as i32);
+            for j in 0..npts {
+                coverage[self.shapes[i].cache[tci + j]] += contrib;
+            }
+        }
+
+        let cell_cost = |cov: i32, target: i8, mt: i32| -> i64 { (cov - target as i32).rem_euclid(mt) as i64 };
+
+        let mut cost: i64 = coverage.iter().zip(self.mat.iter()).map(|(&c, &m)| cell_cost(c, m, mt)).sum();
+        let mut best_assignment = assignment.clone();
+        let mut best_cost = cost;
+
+        const T0: f64 = 5.0;
+        const T1: f64 = 0.02;
+        let start = std::time::Instant::now();
+        let mut iter_count: u64 = 0;
+
+        loop {
+            iter_count += 1;
+            if iter_count % 1024 == 0 && CANCEL_FLAG.load(Ordering::Relaxed) {
+                break;
+            }
+            let elapsed = start.elapsed().as_millis() as u64;
+            if elapsed >= millis {
+                break;
+            }
+            let frac = elapsed as f64 / millis as f64;
+            let temp = T0 * (T1 / T0).powf(frac);
+
+            let shape_i = rng.next_range(ns);
+            let npts = self.shapes[shape_i].npts;
+            let tot = self.shapes[shape_i].tot;
+            if tot <= 1 {
+                continue;
+            }
+            let old_s = assignment[shape_i];
+            let new_s = rng.next_range(tot);
+            if new_s == old_s {
+                continue;
+            }
+
+            let cache = &self.shapes[shape_i].cache;
+            let contrib = -(self.shapes[shape_i].direction as i32);
+            let old_tci = old_s * npts;
+            let new_tci = new_s * npts;
+
+            let mut delta: i64 = 0;
+            for j in 0..npts {
+                let mi = cache[old_tci + j];
+                let before = cell_cost(coverage[mi], self.mat[mi], mt);
+                let after = cell_cost(coverage[mi] - contrib, self.mat[mi], mt);
+                delta += after - before;
+                coverage[mi] -= contrib;
+            }
+            for j in 0..npts {
+                let mi = cache[new_tci + j];
+                let before = cell_cost(coverage[mi], self.mat[mi], mt);
+                let after = cell_cost(coverage[mi] + contrib, self.mat[mi], mt);
+                delta += after - before;
+                coverage[mi] += contrib;
+            }
+
+            let accept = delta <= 0 || rng.next_f64() < (-(delta as f64) / temp).exp();
+            if accept {
+                assignment[shape_i] = new_s;
+                cost += delta;
+                if cost < best_cost {
+                    best_cost = cost;
+                    best_assignment = assignment.clone();
+                }
+                if cost == 0 {
+                    return Some(self.build_solution_from_seq(&assignment));
+                }
+            } else {
+                for j in 0..npts {
+                    coverage[cache[new_tci + j]] -= contrib;
+                }
+                for j in 0..npts {
+                    coverage[cache[old_tci + j]] += contrib;
+                }
+            }
+        }
+
+        if best_cost == 0 {
+            Some(self.build_solution_from_seq(&best_assignment))
+        } else {
+            None
+        }
+    }
+
+    /// Exact search with the same feasibility rule as `solve_dfs`, but
+    /// expansion is ordered by a `BinaryHeap` instead of fixed shape order.
+    /// A child whose mat has a nonzero cell no remaining shape can reach is
+    /// a dead branch and is dropped on the spot rather than pushed.
+    fn solve_best_first(&mut self, on_progress: &mut dyn FnMut(ProgressEvent)) -> Option<Vec<SolutionStep>> {
+        CANCEL_FLAG.store(false, Ordering::SeqCst);
+        let ns = self.ns;
+        let mt = self.max_token as i8;
+        let budget_prunable = self.budget_prunable;
+        let total_cells = self.mat.len();
+        let start = std::time::Instant::now();
+
+        // reachable_from[i][c] = some shape in shapes[i..ns] can cover cell c.
+        let mut reachable_from = vec![vec![false; total_cells]; ns + 1];
+        for i in (0..ns).rev() {
+            let mut r = reachable_from[i + 1].clone();
+            for (c, covers) in self.shapes[i].reach.iter().enumerate() {
+                if *covers {
+                    r[c] = true;
+                }
+            }
+            reachable_from[i] = r;
+        }
+
+        let mut heap = BinaryHeap::new();
+        heap.push(BestFirstNode {
+            i: 0,
+            seq: Vec::with_capacity(ns),
+            mat: self.mat.clone(),
+            budget: self.shapes[0].incs,
+            score: Self::best_first_score(0, &self.mat, &reachable_from[0]),
+        });
+
+        let mut iter_count: u64 = 0;
+        let mut deepest_i = 0usize;
+        let mut best_cells_remaining = self.mat.iter().filter(|&&v| v != 0).count();
+        while let Some(node) = heap.pop() {
+            iter_count += 1;
+            if iter_count % 1024 == 0 {
+                if CANCEL_FLAG.load(Ordering::Relaxed) {
+                    return None;
+                }
+                if let Some(deadline) = self.deadline_millis {
+                    if start.elapsed().as_millis() as u64 > deadline {
+                        return None;
+                    }
+                }
+                let cells_remaining = node.mat.iter().filter(|&&v| v != 0).count();
+                if node.i > deepest_i {
+                    deepest_i = node.i;
+                    best_cells_remaining = cells_remaining;
+                } else if node.i == deepest_i {
+                    best_cells_remaining = best_cells_remaining.min(cells_remaining);
+                }
+                on_progress(ProgressEvent {
+                    iterations: iter_count,
+                    depth_reached: deepest_i,
+                    best_cells_remaining,
+                });
+            }
+
+            if node.i == ns {
+                if node.mat.iter().all(|&v| v == 0) {
+                    return Some(self.build_solution_from_seq(&node.seq));
+                }
+                continue;
+            }
+
+            let i = node.i;
+            let npts = self.shapes[i].npts;
+            let tot = self.shapes[i].tot;
+            let cache = &self.shapes[i].cache;
+            let direction = self.shapes[i].direction;
+            let min_s = self.shapes[i].equivalent_to.map(|ei| node.seq[ei]).unwrap_or(0);
+
+            for s in min_s..tot {
+                let tci = s * npts;
+                let mut ti = node.budget;
+                let mut can_place = true;
+                for j in 0..npts {
+                    let val = node.mat[cache[tci + j]];
+                    let wraps = if direction < 0 { val == 0 } else { val == mt - 1 };
+                    if wraps && budget_prunable {
+                        ti -= 1;
+                        if ti < 0 {
+                            can_place = false;
+                            break;
+                        }
+                    }
+                }
+                if !can_place {
+                    continue;
+                }
+
+                let mut mat = node.mat.clone();
+                for j in 0..npts {
+                    let mi = cache[tci + j];
+                    mat[mi] = stamp_value(mat[mi], mt, direction);
+                }
+
+                if Self::count_unreachable(&mat, &reachable_from[i + 1]) > 0 {
+                    continue;
+                }
+
+                let mut seq = node.seq.clone();
+                seq.push(s);
+                let score = Self::best_first_score(i + 1, &mat, &reachable_from[i + 1]);
+                heap.push(BestFirstNode { i: i + 1, seq, mat, budget: ti, score });
+            }
+        }
+
+        None
+    }
+
+    fn count_unreachable(mat: &[i8], reachable: &[bool]) -> i64 {
+        mat.iter().enumerate().filter(|&(c, &v)| v != 0 && !reachable[c]).count() as i64
+    }
+
+    fn best_first_score(depth: usize, mat: &[i8], reachable: &[bool]) -> i64 {
+        let remaining = mat.iter().filter(|&&v| v != 0).count() as i64;
+        depth as i64 * 1_000_000 - remaining - Self::count_unreachable(mat, reachable)
+    }
+
+    fn build_solution_from_seq(&self, seq: &[usize]) -> Vec<SolutionStep> {
+        let mut steps = Vec::with_capacity(self.ns);
+        for m in 0..self.ns {
+            if let Some((idx, s)) = self.shapes.iter().enumerate().find(|(_, s)| s.original_id == m) {
+                let sq = seq[idx];
+                steps.push(SolutionStep {
+                    original_shape_id: s.original_id,
+                    placement_x: sq % s.ax,
+                    placement_y: sq / s.ax,
+                    placement_seq: sq,
+                });
+            }
+        }
+        steps
+    }
 }